@@ -7,7 +7,10 @@ use log::debug;
 use cairo;
 use cairo::Error;
 
-use qmetaobject::{QRectF, QColor, QPen};
+use image;
+use freetype;
+
+use qmetaobject::{QRectF, QColor, QPen, QPainterPath, QBrush, QFont, QPointF, QImage};
 
 use crate::ofd::Ofd;
 use crate::document::Document;
@@ -52,31 +55,493 @@ impl Renderable for Page {
     }
 }
 
+// A single segment of a path after `AbbreviatedData` has been parsed and
+// all quadratics/arcs have been promoted/decomposed into cubics, in
+// millimetres relative to the boundary origin.
+#[derive(Debug, Clone, Copy)]
+enum PathSegment {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    CurveTo(f64, f64, f64, f64, f64, f64),
+    ClosePath,
+}
+
+// Parses an OFD `AbbreviatedData` string into a flat list of `PathSegment`s.
+// The grammar is a whitespace separated stream of a command letter followed
+// by a fixed number of millimetre coordinates:
+//   S x y / M x y   move to
+//   L x y           line to
+//   Q x1 y1 x2 y2   quadratic bezier, promoted to a cubic
+//   B x1 y1 .. y3   cubic bezier
+//   A rx ry angle large sweep x y   elliptical arc, decomposed into cubics
+//   C               close path
+fn _parse_abbreviated_data(data: &str) -> Vec<PathSegment> {
+    let mut tokens = data.split_whitespace();
+    let mut segments = Vec::new();
+    let mut current = (0.0_f64, 0.0_f64);
+
+    while let Some(cmd) = tokens.next() {
+        if _parse_abbreviated_command(cmd, &mut tokens, &mut current,
+            &mut segments).is_none() {
+            debug!("malformed AbbreviatedData near command {:?}, \
+                stopping path parse", cmd);
+            break;
+        }
+    }
+
+    segments
+}
+
+// Parses a single command and its coordinates, advancing `tokens` and
+// appending to `segments`. Returns `None` (without panicking) if the
+// command's coordinates are missing or not valid floats, so a truncated or
+// malformed `AbbreviatedData` string degrades to a partial path instead of
+// taking down the whole render.
+fn _parse_abbreviated_command(cmd: &str, tokens: &mut std::str::SplitWhitespace,
+    current: &mut (f64, f64), segments: &mut Vec<PathSegment>) -> Option<()> {
+    let mut next_f64 = || -> Option<f64> { tokens.next()?.parse().ok() };
+
+    match cmd {
+        "S" | "M" => {
+            let x = next_f64()?;
+            let y = next_f64()?;
+            *current = (x, y);
+            segments.push(PathSegment::MoveTo(x, y));
+        }
+        "L" => {
+            let x = next_f64()?;
+            let y = next_f64()?;
+            *current = (x, y);
+            segments.push(PathSegment::LineTo(x, y));
+        }
+        "Q" => {
+            let x1 = next_f64()?;
+            let y1 = next_f64()?;
+            let x = next_f64()?;
+            let y = next_f64()?;
+            segments.push(_quadratic_to_cubic(*current, (x1, y1), (x, y)));
+            *current = (x, y);
+        }
+        "B" => {
+            let x1 = next_f64()?;
+            let y1 = next_f64()?;
+            let x2 = next_f64()?;
+            let y2 = next_f64()?;
+            let x = next_f64()?;
+            let y = next_f64()?;
+            *current = (x, y);
+            segments.push(PathSegment::CurveTo(x1, y1, x2, y2, x, y));
+        }
+        "A" => {
+            let rx = next_f64()?;
+            let ry = next_f64()?;
+            let angle = next_f64()?;
+            let large_arc = next_f64()? != 0.0;
+            let sweep = next_f64()? != 0.0;
+            let x = next_f64()?;
+            let y = next_f64()?;
+            for curve in _arc_to_cubics(*current, rx, ry, angle,
+                large_arc, sweep, (x, y)) {
+                segments.push(curve);
+            }
+            *current = (x, y);
+        }
+        "C" => segments.push(PathSegment::ClosePath),
+        _ => (),
+    }
+
+    Some(())
+}
+
+fn _quadratic_to_cubic(current: (f64, f64), control: (f64, f64),
+    end: (f64, f64)) -> PathSegment {
+    let x1 = current.0 + 2.0 / 3.0 * (control.0 - current.0);
+    let y1 = current.1 + 2.0 / 3.0 * (control.1 - current.1);
+    let x2 = end.0 + 2.0 / 3.0 * (control.0 - end.0);
+    let y2 = end.1 + 2.0 / 3.0 * (control.1 - end.1);
+    PathSegment::CurveTo(x1, y1, x2, y2, end.0, end.1)
+}
+
+// Converts an SVG/OFD style endpoint-parameterized elliptical arc into one
+// or more cubic bezier segments, splitting the swept angle into pieces no
+// larger than 90 degrees so the cubic approximation stays accurate.
+fn _arc_to_cubics(current: (f64, f64), rx: f64, ry: f64, angle_deg: f64,
+    large_arc: bool, sweep: bool, end: (f64, f64)) -> Vec<PathSegment> {
+    let (x0, y0) = current;
+    let (x1, y1) = end;
+
+    if rx.abs() < f64::EPSILON || ry.abs() < f64::EPSILON {
+        return vec![PathSegment::LineTo(x1, y1)];
+    }
+
+    let phi = angle_deg.to_radians();
+    let (cos_phi, sin_phi) = (phi.cos(), phi.sin());
+
+    // Endpoint to center parameterization (SVG 1.1 appendix F.6.5).
+    let dx2 = (x0 - x1) / 2.0;
+    let dy2 = (y0 - y1) / 2.0;
+    let x1p = cos_phi * dx2 + sin_phi * dy2;
+    let y1p = -sin_phi * dx2 + cos_phi * dy2;
+
+    let mut rx = rx.abs();
+    let mut ry = ry.abs();
+    let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+    if lambda > 1.0 {
+        let scale = lambda.sqrt();
+        rx *= scale;
+        ry *= scale;
+    }
+
+    let sign = if large_arc != sweep { 1.0 } else { -1.0 };
+    let num = (rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p)
+        .max(0.0);
+    let den = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+    let co = if den > 0.0 { sign * (num / den).sqrt() } else { 0.0 };
+    let cxp = co * rx * y1p / ry;
+    let cyp = -co * ry * x1p / rx;
+
+    let cx = cos_phi * cxp - sin_phi * cyp + (x0 + x1) / 2.0;
+    let cy = sin_phi * cxp + cos_phi * cyp + (y0 + y1) / 2.0;
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| -> f64 {
+        let dot = ux * vx + uy * vy;
+        let len = ((ux * ux + uy * uy) * (vx * vx + vy * vy)).sqrt();
+        let mut a = (dot / len).clamp(-1.0, 1.0).acos();
+        if ux * vy - uy * vx < 0.0 {
+            a = -a;
+        }
+        a
+    };
+
+    let theta1 = angle(1.0, 0.0, (x1p - cxp) / rx, (y1p - cyp) / ry);
+    let mut delta_theta = angle((x1p - cxp) / rx, (y1p - cyp) / ry,
+        (-x1p - cxp) / rx, (-y1p - cyp) / ry);
+    if !sweep && delta_theta > 0.0 {
+        delta_theta -= 2.0 * std::f64::consts::PI;
+    } else if sweep && delta_theta < 0.0 {
+        delta_theta += 2.0 * std::f64::consts::PI;
+    }
+
+    let segment_count = (delta_theta.abs() / (std::f64::consts::PI / 2.0))
+        .ceil().max(1.0) as usize;
+    let step = delta_theta / segment_count as f64;
+
+    let mut segments = Vec::with_capacity(segment_count);
+    let mut theta = theta1;
+    for _ in 0..segment_count {
+        let theta_end = theta + step;
+        let alpha = (4.0 / 3.0) * (step / 4.0).tan();
+
+        let point_at = |t: f64| -> (f64, f64, f64, f64) {
+            let ct = t.cos();
+            let st = t.sin();
+            let ex = cos_phi * rx * ct - sin_phi * ry * st;
+            let ey = sin_phi * rx * ct + cos_phi * ry * st;
+            let tx = -cos_phi * rx * st - sin_phi * ry * ct;
+            let ty = -sin_phi * rx * st + cos_phi * ry * ct;
+            (cx + ex, cy + ey, tx, ty)
+        };
+
+        let (sx, sy, sdx, sdy) = point_at(theta);
+        let (ex, ey, edx, edy) = point_at(theta_end);
+
+        let c1x = sx + alpha * sdx;
+        let c1y = sy + alpha * sdy;
+        let c2x = ex - alpha * edx;
+        let c2y = ey - alpha * edy;
+
+        segments.push(PathSegment::CurveTo(c1x, c1y, c2x, c2y, ex, ey));
+        theta = theta_end;
+    }
+
+    segments
+}
+
+// Parses a space-separated list of on/off dash lengths in millimetres into
+// pixels. An empty or absent pattern means a solid line. Tokens that aren't
+// valid floats are skipped rather than panicking, so a malformed
+// `DashPattern` degrades to a shorter (or solid) dash instead of taking
+// down the render.
+fn _parse_dash_pattern(pattern: &str) -> Vec<f64> {
+    pattern.split_whitespace()
+        .filter_map(|token| token.parse().ok())
+        .map(mmtopx)
+        .collect()
+}
+
+fn _cairo_extend(extend: ct::Extend) -> cairo::Extend {
+    match extend {
+        ct::Extend::Pad => cairo::Extend::Pad,
+        ct::Extend::Reflect => cairo::Extend::Reflect,
+        ct::Extend::Repeat => cairo::Extend::Repeat,
+        ct::Extend::None => cairo::Extend::None,
+    }
+}
+
+fn _add_color_stops(gradient: &impl cairo::Gradient, segments: &[ct::Segment]) {
+    for segment in segments {
+        let color = ct::Color::from(segment.color.clone());
+        let alpha = segment.alpha.unwrap_or(255) as f64 / 255.0;
+        gradient.add_color_stop_rgba(segment.position,
+            color.value[0] as f64 / 255.0,
+            color.value[1] as f64 / 255.0,
+            color.value[2] as f64 / 255.0,
+            alpha);
+    }
+}
+
+// Sets the cairo source for a `Color`: a solid RGB fill, or, when the color
+// carries an `AxialShd`/`RadialShd`, a linear/radial gradient built from its
+// color `Segment`s.
+fn _set_source_for_color(context: &mut cairo::Context, color: &Color) -> Result<(), Error> {
+    if let Some(shd) = color.axial_shd.as_ref() {
+        let gradient = cairo::LinearGradient::new(
+            mmtopx(shd.start_point.0), mmtopx(shd.start_point.1),
+            mmtopx(shd.end_point.0), mmtopx(shd.end_point.1));
+        _add_color_stops(&gradient, &shd.segments);
+        gradient.set_extend(_cairo_extend(shd.extend));
+        context.set_source(&gradient)
+    } else if let Some(shd) = color.radial_shd.as_ref() {
+        let gradient = cairo::RadialGradient::new(
+            mmtopx(shd.start_center.0), mmtopx(shd.start_center.1),
+            mmtopx(shd.start_radius),
+            mmtopx(shd.end_center.0), mmtopx(shd.end_center.1),
+            mmtopx(shd.end_radius));
+        _add_color_stops(&gradient, &shd.segments);
+        gradient.set_extend(_cairo_extend(shd.extend));
+        context.set_source(&gradient)
+    } else {
+        let solid = ct::Color::from(color.value.clone());
+        context.set_source_rgb(solid.value[0] as f64 / 255.0,
+            solid.value[1] as f64 / 255.0,
+            solid.value[2] as f64 / 255.0);
+        Ok(())
+    }
+}
+
+// Applies a clip area's own CTM (distinct from the object's CTM) to a point
+// already expressed in object-local pixels, per the same
+// `x'=ax+cy+e`/`y'=bx+dy+f` convention as `ct::Matrix` elsewhere in this
+// module.
+fn _apply_clip_ctm(ctm: Option<&ct::Matrix>, x: f64, y: f64) -> (f64, f64) {
+    match ctm {
+        Some(m) => (m.a * x + m.c * y + m.e, m.b * x + m.d * y + m.f),
+        None => (x, y),
+    }
+}
+
+// Builds and applies each `Clip`'s path (an `AbbreviatedData` path, or
+// failing that its `boundary`) as a cairo clip, intersected via repeated
+// `context.clip()` calls. `boundary` is the pixel boundary of the object
+// currently being rendered, against which the clip's own boundary is
+// positioned. Released automatically by the caller's `context.restore()`.
+fn _apply_clips_to_cairo(context: &mut cairo::Context, clips: Option<&Clips>,
+    boundary: &ct::PixelBox) -> Result<(), Error> {
+    let clips = match clips {
+        Some(clips) => clips,
+        None => return Ok(()),
+    };
+
+    for clip in clips.clip.iter() {
+        let area = &clip.area;
+        let clip_boundary = area.boundary.as_ref()
+            .map(|b| ct::Box::from(b.clone()).to_pixel());
+        let (origin_x, origin_y) = clip_boundary.as_ref()
+            .map(|b| (b.x as f64, b.y as f64))
+            .unwrap_or((boundary.x as f64, boundary.y as f64));
+        let local_x = origin_x - boundary.x as f64;
+        let local_y = origin_y - boundary.y as f64;
+        let ctm = area.ctm.as_ref().map(|c| ct::Matrix::from(c.clone()));
+
+        // Cairo's context.clip() always intersects with whatever clip
+        // region is already active, so repeated calls here correctly
+        // combine multiple Clips.
+        match area.abbreviated_data.as_ref() {
+            Some(data) => {
+                for segment in _parse_abbreviated_data(data.as_str()) {
+                    match segment {
+                        PathSegment::MoveTo(x, y) => {
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            context.move_to(local_x + x, local_y + y);
+                        }
+                        PathSegment::LineTo(x, y) => {
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            context.line_to(local_x + x, local_y + y);
+                        }
+                        PathSegment::CurveTo(x1, y1, x2, y2, x, y) => {
+                            let (x1, y1) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x1), mmtopx(y1));
+                            let (x2, y2) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x2), mmtopx(y2));
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            context.curve_to(local_x + x1, local_y + y1,
+                                local_x + x2, local_y + y2,
+                                local_x + x, local_y + y);
+                        }
+                        PathSegment::ClosePath => context.close_path(),
+                    }
+                }
+            }
+            None => {
+                if let Some(b) = clip_boundary.as_ref() {
+                    context.rectangle(local_x, local_y,
+                        b.width as f64, b.height as f64);
+                }
+            }
+        }
+        context.clip();
+    }
+
+    Ok(())
+}
+
+fn _apply_clips_to_qpainter(painter: &mut qmetaobject::QPainter,
+    clips: Option<&Clips>, boundary: &ct::PixelBox) {
+    let clips = match clips {
+        Some(clips) => clips,
+        None => return,
+    };
+
+    for clip in clips.clip.iter() {
+        let area = &clip.area;
+        let clip_boundary = area.boundary.as_ref()
+            .map(|b| ct::Box::from(b.clone()).to_pixel());
+        let (origin_x, origin_y) = clip_boundary.as_ref()
+            .map(|b| (b.x as f64, b.y as f64))
+            .unwrap_or((boundary.x as f64, boundary.y as f64));
+        let local_x = origin_x - boundary.x as f64;
+        let local_y = origin_y - boundary.y as f64;
+        let ctm = area.ctm.as_ref().map(|c| ct::Matrix::from(c.clone()));
+
+        // QPainter's clip setters default to Qt::ReplaceClip, which would
+        // drop all but the last Clip; IntersectClip matches cairo's
+        // always-intersecting context.clip() so multiple Clips combine.
+        match area.abbreviated_data.as_ref() {
+            Some(data) => {
+                let mut path = QPainterPath::default();
+                for segment in _parse_abbreviated_data(data.as_str()) {
+                    match segment {
+                        PathSegment::MoveTo(x, y) => {
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            path.move_to(local_x + x, local_y + y);
+                        }
+                        PathSegment::LineTo(x, y) => {
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            path.line_to(local_x + x, local_y + y);
+                        }
+                        PathSegment::CurveTo(x1, y1, x2, y2, x, y) => {
+                            let (x1, y1) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x1), mmtopx(y1));
+                            let (x2, y2) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x2), mmtopx(y2));
+                            let (x, y) = _apply_clip_ctm(ctm.as_ref(),
+                                mmtopx(x), mmtopx(y));
+                            path.cubic_to(local_x + x1, local_y + y1,
+                                local_x + x2, local_y + y2,
+                                local_x + x, local_y + y);
+                        }
+                        PathSegment::ClosePath => path.close_sub_path(),
+                    }
+                }
+                painter.set_clip_path(path, qmetaobject::ClipOperation::IntersectClip);
+            }
+            None => {
+                if let Some(b) = clip_boundary.as_ref() {
+                    painter.set_clip_rect(QRectF {
+                        x: local_x,
+                        y: local_y,
+                        width: b.width as f64,
+                        height: b.height as f64,
+                    }, qmetaobject::ClipOperation::IntersectClip);
+                }
+            }
+        }
+    }
+}
+
 impl Renderable for PathObject {
     fn render_to_cairo_context(&self, context: &mut cairo::Context,
         _ofd: &mut Ofd, _document: &Document) -> Result<(), Error> {
         context.save()?;
 
-        // TODO(hualet): implement ctm.
         let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
-        let color = ct::Color::from(
-            self.stroke_color.as_ref().unwrap().value.clone());
+        context.translate(boundary.x as f64, boundary.y as f64);
+        if let Some(ctm) = self.ctm.as_ref() {
+            let matrix = ct::Matrix::from(ctm.clone());
+            context.transform(matrix.into());
+        }
+        _apply_clips_to_cairo(context, self.clips.as_ref(), &boundary)?;
 
-        context.set_source_rgb(color.value[0] as f64 / 255.0,
-            color.value[1] as f64 / 255.0,
-            color.value[2] as f64 / 255.0);
         context.set_line_width(mmtopx(self.line_width));
+        context.set_line_cap(match self.cap {
+            Cap::Round => cairo::LineCap::Round,
+            Cap::Square => cairo::LineCap::Square,
+            Cap::Butt => cairo::LineCap::Butt,
+        });
+        context.set_line_join(match self.join {
+            Join::Round => cairo::LineJoin::Round,
+            Join::Bevel => cairo::LineJoin::Bevel,
+            Join::Miter => cairo::LineJoin::Miter,
+        });
+        context.set_miter_limit(self.miter_limit);
+        match self.dash_pattern.as_ref() {
+            Some(pattern) if !pattern.trim().is_empty() => {
+                let dashes = _parse_dash_pattern(pattern.as_str());
+                context.set_dash(&dashes, mmtopx(self.dash_offset));
+            }
+            _ => context.set_dash(&[], 0.),
+        }
 
-        context.move_to(boundary.x as f64, boundary.y as f64);
-        context.line_to((boundary.x + boundary.width) as f64,
-            boundary.y as f64);
-        context.line_to((boundary.x + boundary.width) as f64,
-            (boundary.y + boundary.height) as f64);
-        context.line_to(boundary.x as f64,
-            (boundary.y + boundary.height) as f64);
-        context.line_to(boundary.x as f64, boundary.y as f64);
+        match self.abbreviated_data.as_ref() {
+            Some(data) => {
+                for segment in _parse_abbreviated_data(data.as_str()) {
+                    match segment {
+                        PathSegment::MoveTo(x, y) => context.move_to(
+                            mmtopx(x), mmtopx(y)),
+                        PathSegment::LineTo(x, y) => context.line_to(
+                            mmtopx(x), mmtopx(y)),
+                        PathSegment::CurveTo(x1, y1, x2, y2, x, y) =>
+                            context.curve_to(
+                                mmtopx(x1), mmtopx(y1),
+                                mmtopx(x2), mmtopx(y2),
+                                mmtopx(x), mmtopx(y)),
+                        PathSegment::ClosePath => context.close_path(),
+                    }
+                }
+            }
+            None => {
+                context.move_to(0., 0.);
+                context.line_to(boundary.width as f64, 0.);
+                context.line_to(boundary.width as f64, boundary.height as f64);
+                context.line_to(0., boundary.height as f64);
+                context.line_to(0., 0.);
+            }
+        }
+
+        // The path above is built once and painted up to twice (fill then
+        // stroke) so fill_preserve() keeps it around for the stroke.
+        if self.fill {
+            // `Fill` can be set while the color itself is left to inherit
+            // from a DrawParam/default, so fall back rather than unwrap.
+            _set_source_for_color(context,
+                self.fill_color.as_ref().unwrap_or(&Color::default()))?;
+            context.set_fill_rule(match self.rule {
+                Rule::EvenOdd => cairo::FillRule::EvenOdd,
+                Rule::NonZero => cairo::FillRule::Winding,
+            });
+            context.fill_preserve()?;
+        }
 
-        context.stroke()?;
+        if self.stroke {
+            _set_source_for_color(context,
+                self.stroke_color.as_ref().unwrap_or(&Color::default()))?;
+            context.stroke()?;
+        }
 
         context.restore()
     }
@@ -87,32 +552,194 @@ impl Renderable for PathObject {
 
         painter.save();
 
-        // TODO(hualet): implement ctm.
         let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
-        let color = ct::Color::from(
-            self.stroke_color.as_ref().unwrap().value.clone());
-
-        let pen_color = QColor::from_rgb(color.value[0], color.value[1],
-            color.value[2]);
-        let mut pen = QPen::from_color(pen_color);
-        pen.set_width(mmtopx(self.line_width) as i32);
-        painter.set_pen(pen);
-
-        let rect = QRectF {
-            x: boundary.x as f64,
-            y: boundary.y as f64,
-            width: boundary.width as f64,
-            height: boundary.height as f64
-        };
-        painter.draw_rect(rect);
+        painter.translate(boundary.x as f64, boundary.y as f64);
+        if let Some(ctm) = self.ctm.as_ref() {
+            let matrix = ct::Matrix::from(ctm.clone());
+            painter.set_world_transform(matrix.into(), true);
+        }
+        _apply_clips_to_qpainter(painter, self.clips.as_ref(), &boundary);
+
+        if self.stroke {
+            // `Stroke` can be set while the color itself is left to inherit
+            // from a DrawParam/default, so fall back rather than unwrap.
+            let stroke_color = ct::Color::from(
+                self.stroke_color.as_ref().unwrap_or(&Color::default()).value.clone());
+            let pen_color = QColor::from_rgb(stroke_color.value[0],
+                stroke_color.value[1], stroke_color.value[2]);
+            let mut pen = QPen::from_color(pen_color);
+            let line_width = mmtopx(self.line_width);
+            pen.set_width(line_width as i32);
+            pen.set_cap_style(match self.cap {
+                Cap::Round => qmetaobject::PenCapStyle::RoundCap,
+                Cap::Square => qmetaobject::PenCapStyle::SquareCap,
+                Cap::Butt => qmetaobject::PenCapStyle::FlatCap,
+            });
+            pen.set_join_style(match self.join {
+                Join::Round => qmetaobject::PenJoinStyle::RoundJoin,
+                Join::Bevel => qmetaobject::PenJoinStyle::BevelJoin,
+                Join::Miter => qmetaobject::PenJoinStyle::MiterJoin,
+            });
+            pen.set_miter_limit(self.miter_limit);
+            match self.dash_pattern.as_ref() {
+                Some(pattern) if !pattern.trim().is_empty() => {
+                    // Unlike cairo's set_dash, QPen::set_dash_pattern and
+                    // set_dash_offset are expressed in units of the pen
+                    // width, not absolute pixels.
+                    let pen_width = (line_width as i32).max(1) as f64;
+                    let dashes: Vec<f64> = _parse_dash_pattern(pattern.as_str())
+                        .iter().map(|length| length / pen_width).collect();
+                    pen.set_dash_pattern(dashes);
+                    pen.set_dash_offset(mmtopx(self.dash_offset) / pen_width);
+                }
+                _ => (),
+            }
+            painter.set_pen(pen);
+        } else {
+            painter.set_pen(QPen::from_style(qmetaobject::PenStyle::NoPen));
+        }
+
+        let mut path = QPainterPath::default();
+        path.set_fill_rule(match self.rule {
+            Rule::EvenOdd => qmetaobject::FillRule::OddEvenFill,
+            Rule::NonZero => qmetaobject::FillRule::WindingFill,
+        });
+        match self.abbreviated_data.as_ref() {
+            Some(data) => {
+                for segment in _parse_abbreviated_data(data.as_str()) {
+                    match segment {
+                        PathSegment::MoveTo(x, y) => path.move_to(
+                            mmtopx(x), mmtopx(y)),
+                        PathSegment::LineTo(x, y) => path.line_to(
+                            mmtopx(x), mmtopx(y)),
+                        PathSegment::CurveTo(x1, y1, x2, y2, x, y) =>
+                            path.cubic_to(
+                                mmtopx(x1), mmtopx(y1),
+                                mmtopx(x2), mmtopx(y2),
+                                mmtopx(x), mmtopx(y)),
+                        PathSegment::ClosePath => path.close_sub_path(),
+                    }
+                }
+            }
+            None => {
+                let rect = QRectF {
+                    x: 0.,
+                    y: 0.,
+                    width: boundary.width as f64,
+                    height: boundary.height as f64
+                };
+                path.add_rect(rect);
+            }
+        }
+
+        // The path is built once and then painted up to twice: a filled
+        // pass with the brush, then a stroked pass with the pen, mirroring
+        // fill_preserve()+stroke() on the cairo side.
+        if self.fill {
+            // `Fill` can be set while the color itself is left to inherit
+            // from a DrawParam/default, so fall back rather than unwrap.
+            let fill_color = ct::Color::from(
+                self.fill_color.as_ref().unwrap_or(&Color::default()).value.clone());
+            let brush = QBrush::from_color(QColor::from_rgb(
+                fill_color.value[0], fill_color.value[1],
+                fill_color.value[2]));
+            painter.fill_path(path.clone(), brush);
+        }
+        painter.draw_path(path);
 
         painter.restore();
     }
 }
 
+// Expands an OFD `DeltaX`/`DeltaY` run-length string into one explicit
+// offset per glyph. Most tokens are a plain millimetre value; a `g` token
+// means "repeat the following value N times", e.g. `g 3 4.5` expands to
+// `[4.5, 4.5, 4.5]`. A malformed run (missing or non-numeric count/value)
+// stops expansion at that point rather than panicking, so a bad DeltaX/
+// DeltaY string degrades to default glyph advances for the remaining
+// glyphs instead of taking down the render.
+fn _expand_delta(deltas: &str) -> Vec<f64> {
+    let mut tokens = deltas.split_whitespace();
+    let mut expanded = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        if token == "g" {
+            let count = tokens.next().and_then(|t| t.parse::<usize>().ok());
+            let value = tokens.next().and_then(|t| t.parse::<f64>().ok());
+            match (count, value) {
+                (Some(count), Some(value)) =>
+                    expanded.extend(std::iter::repeat(value).take(count)),
+                _ => {
+                    debug!("malformed DeltaX/DeltaY run near {:?}, \
+                        stopping expansion", token);
+                    break;
+                }
+            }
+        } else {
+            match token.parse() {
+                Ok(value) => expanded.push(value),
+                Err(_) => {
+                    debug!("malformed DeltaX/DeltaY token {:?}, \
+                        stopping expansion", token);
+                    break;
+                }
+            }
+        }
+    }
+
+    expanded
+}
+
+// Process-wide cache of built font faces, keyed by embedded font resource
+// path. `cairo::FontFace` is cheap to clone (it's a reference-counted FFI
+// handle), so every `TextObject` that shares a font reuses one entry
+// instead of re-reading the font from the zip and leaking a new FreeType
+// Library/Face on every paint.
+fn _font_face_cache() -> &'static std::sync::Mutex<std::collections::HashMap<String, cairo::FontFace>> {
+    static CACHE: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, cairo::FontFace>>> =
+        std::sync::OnceLock::new();
+    CACHE.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+// Loads an embedded `FontFile` resource from the zip archive and builds a
+// FreeType-backed cairo font face for it, reusing a cached face when one
+// has already been built for the same resource path.
+fn _load_embedded_font_face(ofd: &mut Ofd, document: &Document,
+    font_file: &str) -> cairo::FontFace {
+    let path = Path::new(ofd.node.doc_body.doc_root.as_str());
+    let res_path = path.parent().unwrap()
+        .join(document.public_res.base_loc.as_str())
+        .join(font_file);
+    let cache_key = res_path.to_string_lossy().into_owned();
+
+    let cache = _font_face_cache();
+    if let Some(face) = cache.lock().unwrap().get(&cache_key) {
+        return face.clone();
+    }
+
+    let mut file = ofd.zip_archive.by_name(res_path.to_str().unwrap()).unwrap();
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).unwrap();
+
+    // cairo::FontFace::create_from_ft does not take ownership of (or extend
+    // the lifetime of) the FreeType library/face it wraps, so both must
+    // outlive every render that uses the returned FontFace. Leak them for
+    // the life of the process - once per distinct font resource, thanks to
+    // the cache above - rather than let them drop at the end of this
+    // function and leave the FontFace pointing at freed FreeType memory.
+    let library: &'static freetype::Library =
+        Box::leak(Box::new(freetype::Library::init().unwrap()));
+    let ft_face: &'static freetype::Face =
+        Box::leak(Box::new(library.new_memory_face(content, 0).unwrap()));
+    let face = cairo::FontFace::create_from_ft(ft_face).unwrap();
+
+    cache.lock().unwrap().insert(cache_key, face.clone());
+    face
+}
+
 impl Renderable for TextObject {
     fn render_to_cairo_context(&self, context: &mut cairo::Context,
-        _ofd: &mut Ofd, document: &Document) -> Result<(), Error> {
+        ofd: &mut Ofd, document: &Document) -> Result<(), Error> {
         context.save()?;
 
         let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
@@ -122,9 +749,17 @@ impl Renderable for TextObject {
         let font_id = self.font;
         for font in document.public_res.fonts.font.iter() {
             if font.id == font_id {
-                // TODO(hualet): custom font file loading.
-                context.select_font_face(font.family_name.as_str(),
-                    cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+                match font.font_file.as_ref() {
+                    Some(font_file) => {
+                        let face = _load_embedded_font_face(ofd, document,
+                            font_file.as_str());
+                        context.set_font_face(&face);
+                    }
+                    None => {
+                        context.select_font_face(font.family_name.as_str(),
+                            cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+                    }
+                }
                 break;
             }
         }
@@ -137,8 +772,7 @@ impl Renderable for TextObject {
         // NOTE(hualet): transform should be used together with translate,
         // so the coordinate system is correct.
         // THEY ARE BOTH TRANSFORMATIONS!
-        context.translate(boundary.x as f64 + mmtopx(self.text_code.x),
-            boundary.y as f64 + mmtopx(self.text_code.y));
+        context.translate(boundary.x as f64, boundary.y as f64);
         if let Some(ctm) = self.ctm.as_ref() {
             debug!("render text object:{:?} with ctm: {:?}",
                 self.text_code.value, ctm);
@@ -146,16 +780,93 @@ impl Renderable for TextObject {
             let cairo_matrix: cairo::Matrix = matrix.into();
             context.transform(cairo_matrix);
         }
+        // Clips are built in the same boundary-relative frame PathObject and
+        // ImageObject use, so they must be applied before the text_code
+        // offset below shifts the origin any further.
+        _apply_clips_to_cairo(context, self.clips.as_ref(), &boundary)?;
+        context.translate(mmtopx(self.text_code.x), mmtopx(self.text_code.y));
+
+        let delta_x = self.text_code.delta_x.as_ref()
+            .map(|d| _expand_delta(d.as_str()));
+        let delta_y = self.text_code.delta_y.as_ref()
+            .map(|d| _expand_delta(d.as_str()));
 
-        context.move_to(0., 0.);
-        context.show_text(self.text_code.value.as_str())?;
+        let (mut x, mut y) = (0., 0.);
+        for (i, glyph) in self.text_code.value.chars().enumerate() {
+            context.move_to(x, y);
+            let glyph = glyph.to_string();
+            context.show_text(glyph.as_str())?;
+
+            x += match delta_x.as_ref().and_then(|d| d.get(i)) {
+                Some(dx) => mmtopx(*dx),
+                None => context.text_extents(glyph.as_str())?.x_advance(),
+            };
+            if let Some(dy) = delta_y.as_ref().and_then(|d| d.get(i)) {
+                y += mmtopx(*dy);
+            }
+        }
 
         context.restore()
     }
 
-    fn render_to_qpainter(&self, qpainter: &mut qmetaobject::QPainter,
-        _ofd: &mut Ofd, _document: &Document) {
+    fn render_to_qpainter(&self, painter: &mut qmetaobject::QPainter,
+        ofd: &mut Ofd, document: &Document) {
         debug!("render text object to qpainter");
+
+        painter.save();
+
+        let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
+        let color = self.fill_color.as_ref().unwrap_or(&Color::default()).value.clone();
+        let fill_color = ct::Color::from(color);
+
+        let font_id = self.font;
+        let mut family_name = String::from("sans-serif");
+        for font in document.public_res.fonts.font.iter() {
+            if font.id == font_id {
+                family_name = font.family_name.clone();
+                break;
+            }
+        }
+        let mut font = QFont::from_family(family_name.as_str());
+        font.set_pixel_size(mmtopx(self.size) as i32);
+        painter.set_font(font);
+
+        let pen_color = QColor::from_rgb(fill_color.value[0],
+            fill_color.value[1], fill_color.value[2]);
+        painter.set_pen(QPen::from_color(pen_color));
+
+        painter.translate(boundary.x as f64, boundary.y as f64);
+        if let Some(ctm) = self.ctm.as_ref() {
+            let matrix = ct::Matrix::from(ctm.clone());
+            painter.set_world_transform(matrix.into(), true);
+        }
+        // Clips are built in the same boundary-relative frame PathObject and
+        // ImageObject use, so they must be applied before the text_code
+        // offset below shifts the origin any further.
+        _apply_clips_to_qpainter(painter, self.clips.as_ref(), &boundary);
+        painter.translate(mmtopx(self.text_code.x), mmtopx(self.text_code.y));
+
+        let delta_x = self.text_code.delta_x.as_ref()
+            .map(|d| _expand_delta(d.as_str()));
+        let delta_y = self.text_code.delta_y.as_ref()
+            .map(|d| _expand_delta(d.as_str()));
+
+        let metrics = painter.font_metrics();
+        let (mut x, mut y) = (0., 0.);
+        for (i, glyph) in self.text_code.value.chars().enumerate() {
+            let glyph = glyph.to_string();
+            painter.draw_text(QPointF { x, y }, glyph.clone());
+
+            x += match delta_x.as_ref().and_then(|d| d.get(i)) {
+                Some(dx) => mmtopx(*dx),
+                None => metrics.horizontal_advance(glyph.as_str()) as f64,
+            };
+            if let Some(dy) = delta_y.as_ref().and_then(|d| d.get(i)) {
+                y += mmtopx(*dy);
+            }
+        }
+
+        painter.restore();
     }
 }
 
@@ -165,8 +876,13 @@ impl Renderable for ImageObject {
         ofd: &mut Ofd, document: &Document) -> Result<(), Error> {
         context.save()?;
 
-        // TODO(hualet): implement ctm.
         let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
+        context.translate(boundary.x as f64, boundary.y as f64);
+        if let Some(ctm) = self.ctm.as_ref() {
+            let matrix = ct::Matrix::from(ctm.clone());
+            context.transform(matrix.into());
+        }
+        _apply_clips_to_cairo(context, self.clips.as_ref(), &boundary)?;
 
         // find the image file:
         // 1) find the resource file in DocumentRes with the resource id
@@ -183,15 +899,57 @@ impl Renderable for ImageObject {
                 let mut content = Vec::new();
                 let _size = file.read_to_end(&mut content).unwrap();
 
-                let mut file_reader = Cursor::new(content);
-                // FIXME(hualet): png is not for sure.
-                let surface = cairo::ImageSurface::create_from_png(&mut file_reader).unwrap();
-                context.scale(boundary.width/ surface.width() as f64,
-                    boundary.height/ surface.height() as f64);
-                context.set_source_surface(&surface,
-                    boundary.x as f64,
-                    boundary.y as f64)?;
-                context.paint()?;
+                // `image` sniffs the format from content, so PNG, JPEG,
+                // BMP, GIF and TIFF embedded media all decode here. A
+                // corrupt or truncated embedded image degrades to skipping
+                // this object rather than panicking the whole page render,
+                // matching how malformed AbbreviatedData is handled.
+                let decoded = match image::load_from_memory(&content) {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        debug!("failed to decode embedded media {:?}: {}",
+                            resource.media_file, e);
+                        continue;
+                    }
+                };
+                let rgba = decoded.to_rgba8();
+                let (width, height) = (rgba.width() as i32, rgba.height() as i32);
+
+                // Cairo wants premultiplied BGRA for ARgb32.
+                let stride = cairo::Format::ARgb32.stride_for_width(width as u32)
+                    .unwrap();
+                let mut data = vec![0u8; (stride * height) as usize];
+                for (y, row) in rgba.rows().enumerate() {
+                    let row_offset = y * stride as usize;
+                    for (x, pixel) in row.enumerate() {
+                        let [r, g, b, a] = pixel.0;
+                        let alpha = a as f64 / 255.0;
+                        let offset = row_offset + x * 4;
+                        data[offset] = (b as f64 * alpha) as u8;
+                        data[offset + 1] = (g as f64 * alpha) as u8;
+                        data[offset + 2] = (r as f64 * alpha) as u8;
+                        data[offset + 3] = a;
+                    }
+                }
+                let surface = cairo::ImageSurface::create_for_data(data,
+                    cairo::Format::ARgb32, width, height, stride).unwrap();
+
+                let pattern = cairo::SurfacePattern::create(&surface);
+                pattern.set_extend(cairo::Extend::Pad);
+                let mut pattern_matrix = cairo::Matrix::identity();
+                pattern_matrix.scale(width as f64 / boundary.width as f64,
+                    height as f64 / boundary.height as f64);
+                pattern.set_matrix(pattern_matrix);
+
+                context.set_source(&pattern)?;
+                // Clip to the object's own boundary: `paint_with_alpha`
+                // otherwise fills the whole current clip region, and
+                // Extend::Pad would smear the image's edge pixels across it.
+                context.rectangle(0., 0., boundary.width as f64,
+                    boundary.height as f64);
+                context.clip();
+                let object_alpha = self.alpha.unwrap_or(255) as f64 / 255.0;
+                context.paint_with_alpha(object_alpha)?;
             }
         }
 
@@ -199,9 +957,45 @@ impl Renderable for ImageObject {
         context.restore()
     }
 
-    fn render_to_qpainter(&self, qpainter: &mut qmetaobject::QPainter,
+    fn render_to_qpainter(&self, painter: &mut qmetaobject::QPainter,
         ofd: &mut Ofd, document: &Document) {
-        debug!("render pageblock to qpainter");
+        debug!("render image object to qpainter");
+
+        painter.save();
+
+        let boundary = ct::Box::from(self.boundary.clone()).to_pixel();
+        painter.translate(boundary.x as f64, boundary.y as f64);
+        if let Some(ctm) = self.ctm.as_ref() {
+            let matrix = ct::Matrix::from(ctm.clone());
+            painter.set_world_transform(matrix.into(), true);
+        }
+        _apply_clips_to_qpainter(painter, self.clips.as_ref(), &boundary);
+
+        for resource in document.doc_res.multi_medias.multi_media.iter() {
+            if resource.id == self.resource_id {
+                let path = Path::new(ofd.node.doc_body.doc_root.as_str());
+                let res_path = &path.parent().unwrap()
+                    .join(document.doc_res.base_loc.as_str())
+                    .join(resource.media_file.as_str());
+
+                let mut file = ofd.zip_archive.by_name(res_path.to_str().unwrap()).unwrap();
+                let mut content = Vec::new();
+                let _size = file.read_to_end(&mut content).unwrap();
+
+                let mut image = QImage::default();
+                image.load_from_data(&content);
+
+                let rect = QRectF {
+                    x: 0.,
+                    y: 0.,
+                    width: boundary.width as f64,
+                    height: boundary.height as f64,
+                };
+                painter.draw_image(rect, image);
+            }
+        }
+
+        painter.restore();
     }
 }
 
@@ -306,4 +1100,19 @@ impl From<ct::Matrix> for cairo::Matrix {
             value.f  // y0
         )
     }
+}
+
+// QTransform uses the same `x'=m11*x+m21*y+dx`, `y'=m12*x+m22*y+dy`
+// convention as cairo's matrix, so the fields map directly.
+impl From<ct::Matrix> for qmetaobject::QTransform {
+    fn from(value: ct::Matrix) -> Self {
+        Self::new(
+            value.a, // m11
+            value.b, // m12
+            value.c, // m21
+            value.d, // m22
+            value.e, // dx
+            value.f  // dy
+        )
+    }
 }
\ No newline at end of file